@@ -8,6 +8,91 @@ pub struct WorkflowGraphManifest {
     pub workflows: HashMap<String, WorkflowGraph>,
 }
 
+impl WorkflowGraphManifest {
+    // Workflows sorted by id, so multi-workflow output (DOT/GraphML/
+    // diagnostics) is stable across runs instead of following `HashMap`'s
+    // unspecified iteration order.
+    fn sorted_workflows(&self) -> Vec<&WorkflowGraph> {
+        let mut graphs: Vec<&WorkflowGraph> = self.workflows.values().collect();
+        graphs.sort_by(|a, b| a.workflow_id.cmp(&b.workflow_id));
+        graphs
+    }
+
+    // Renders every workflow as a cluster subgraph in a single DOT document,
+    // so the whole manifest can be piped into `dot`/`xdot`/Graphviz tooling.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        out.push_str("digraph Workflows {\n");
+        for graph in self.sorted_workflows() {
+            graph.write_dot(&mut out);
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    // Renders every workflow as a separate <graph> element in one GraphML
+    // document, preserving step_id/line as typed <data> keys.
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str(
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        );
+        out.push_str(
+            "  <key id=\"nodeKind\" for=\"node\" attr.name=\"nodeKind\" attr.type=\"string\"/>\n",
+        );
+        out.push_str(
+            "  <key id=\"stepId\" for=\"node\" attr.name=\"stepId\" attr.type=\"string\"/>\n",
+        );
+        out.push_str("  <key id=\"line\" for=\"node\" attr.name=\"line\" attr.type=\"int\"/>\n");
+        out.push_str(
+            "  <key id=\"edgeType\" for=\"edge\" attr.name=\"edgeType\" attr.type=\"string\"/>\n",
+        );
+        for graph in self.sorted_workflows() {
+            graph.write_graphml(&mut out);
+        }
+        out.push_str("</graphml>\n");
+        out
+    }
+
+    // Walks every workflow's nodes/edges looking for authoring mistakes that
+    // would otherwise only surface at runtime: unreachable/dangling nodes,
+    // dangling workflow-call references, cycles that aren't marked as
+    // loop-back edges, and duplicate step_ids. Each diagnostic carries the
+    // offending node's `line` so it can be translated directly into an
+    // editor/LSP diagnostic.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let known_workflow_ids: std::collections::HashSet<&str> = self
+            .workflows
+            .values()
+            .map(|graph| graph.workflow_id.as_str())
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        for graph in self.sorted_workflows() {
+            graph.diagnose(&known_workflow_ids, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    pub file_path: String,
+    pub line: usize,
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkflowGraph {
@@ -18,6 +103,689 @@ pub struct WorkflowGraph {
     pub edges: Vec<GraphEdge>,
 }
 
+const LAYOUT_VERTICAL_SPACING: f64 = 120.0;
+const LAYOUT_HORIZONTAL_SPACING: f64 = 220.0;
+
+impl WorkflowGraph {
+    // Sugiyama-style layered layout: rank nodes by longest path from `start`,
+    // order each layer by the median position of its neighbors in the
+    // adjacent layer (a few down/up sweeps to settle crossings), then turn
+    // rank/slot into x/y coordinates. Replaces the single-column positions
+    // `GraphBuilder::add_node` assigns as nodes are built.
+    pub fn layout(&mut self) {
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        let ranks = self.compute_ranks();
+        let max_rank = ranks.values().copied().max().unwrap_or(0);
+        let mut layers: Vec<Vec<String>> = vec![Vec::new(); max_rank + 1];
+        for node in &self.nodes {
+            let rank = ranks.get(&node.id).copied().unwrap_or(0);
+            layers[rank].push(node.id.clone());
+        }
+
+        self.order_layers(&mut layers);
+        self.assign_positions(&layers);
+    }
+
+    // rank(start) = 0, rank(n) = 1 + max(rank(pred)) over non-"loop-back"
+    // edges, relaxed to a fixed point so it tolerates any edge order.
+    fn compute_ranks(&self) -> HashMap<String, usize> {
+        let mut rank: HashMap<String, usize> =
+            self.nodes.iter().map(|n| (n.id.clone(), 0)).collect();
+        let forward_edges: Vec<&GraphEdge> = self
+            .edges
+            .iter()
+            .filter(|e| e.edge_type != "loop-back")
+            .collect();
+
+        for _ in 0..self.nodes.len() {
+            let mut changed = false;
+            for edge in &forward_edges {
+                let source_rank = *rank.get(&edge.source).unwrap_or(&0);
+                let candidate = source_rank + 1;
+                let target_rank = rank.entry(edge.target.clone()).or_insert(0);
+                if candidate > *target_rank {
+                    *target_rank = candidate;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        rank
+    }
+
+    fn order_layers(&self, layers: &mut [Vec<String>]) {
+        let mut predecessors: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            if edge.edge_type == "loop-back" {
+                continue;
+            }
+            predecessors
+                .entry(edge.target.as_str())
+                .or_default()
+                .push(edge.source.as_str());
+            successors
+                .entry(edge.source.as_str())
+                .or_default()
+                .push(edge.target.as_str());
+        }
+
+        const SWEEPS: usize = 4;
+        for sweep in 0..SWEEPS {
+            if sweep % 2 == 0 {
+                for i in 1..layers.len() {
+                    let (above, rest) = layers.split_at_mut(i);
+                    reorder_by_median(&mut rest[0], &above[i - 1], &predecessors);
+                }
+            } else {
+                for i in (0..layers.len().saturating_sub(1)).rev() {
+                    let (rest, below) = layers.split_at_mut(i + 1);
+                    reorder_by_median(&mut rest[i], &below[0], &successors);
+                }
+            }
+        }
+    }
+
+    fn assign_positions(&mut self, layers: &[Vec<String>]) {
+        let mut slot_by_id: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (rank, layer) in layers.iter().enumerate() {
+            for (slot, id) in layer.iter().enumerate() {
+                slot_by_id.insert(id.as_str(), (rank, slot));
+            }
+        }
+
+        let max_width = layers.iter().map(|layer| layer.len()).max().unwrap_or(1);
+        for node in &mut self.nodes {
+            if let Some(&(rank, slot)) = slot_by_id.get(node.id.as_str()) {
+                let layer_width = layers[rank].len();
+                let centering_offset =
+                    (max_width as f64 - layer_width as f64) * LAYOUT_HORIZONTAL_SPACING / 2.0;
+                node.position = Position {
+                    x: centering_offset + slot as f64 * LAYOUT_HORIZONTAL_SPACING,
+                    y: rank as f64 * LAYOUT_VERTICAL_SPACING,
+                };
+            }
+        }
+    }
+
+    fn write_dot(&self, out: &mut String) {
+        out.push_str(&format!(
+            "  subgraph \"cluster_{}\" {{\n",
+            escape_dot(&self.workflow_id)
+        ));
+        out.push_str(&format!(
+            "    label=\"{}\";\n",
+            escape_dot(&self.workflow_name)
+        ));
+
+        for node in &self.nodes {
+            let shape = match node.data.node_kind.as_str() {
+                "workflow_start" | "workflow_end" => "ellipse",
+                "workflow" => "box, peripheries=2",
+                _ => "box",
+            };
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", shape={}];\n",
+                escape_dot(&node.id),
+                escape_dot(&node.data.label),
+                shape
+            ));
+        }
+
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                escape_dot(&edge.source),
+                escape_dot(&edge.target),
+                escape_dot(&edge.edge_type)
+            ));
+        }
+
+        out.push_str("  }\n");
+    }
+
+    fn write_graphml(&self, out: &mut String) {
+        out.push_str(&format!(
+            "  <graph id=\"{}\" edgedefault=\"directed\">\n",
+            escape_xml(&self.workflow_id)
+        ));
+
+        for node in &self.nodes {
+            out.push_str(&format!("    <node id=\"{}\">\n", escape_xml(&node.id)));
+            out.push_str(&format!(
+                "      <data key=\"label\">{}</data>\n",
+                escape_xml(&node.data.label)
+            ));
+            out.push_str(&format!(
+                "      <data key=\"nodeKind\">{}</data>\n",
+                escape_xml(&node.data.node_kind)
+            ));
+            if let Some(step_id) = &node.data.step_id {
+                out.push_str(&format!(
+                    "      <data key=\"stepId\">{}</data>\n",
+                    escape_xml(step_id)
+                ));
+            }
+            out.push_str(&format!(
+                "      <data key=\"line\">{}</data>\n",
+                node.data.line
+            ));
+            out.push_str("    </node>\n");
+        }
+
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "    <edge id=\"{}\" source=\"{}\" target=\"{}\">\n",
+                escape_xml(&edge.id),
+                escape_xml(&edge.source),
+                escape_xml(&edge.target)
+            ));
+            out.push_str(&format!(
+                "      <data key=\"edgeType\">{}</data>\n",
+                escape_xml(&edge.edge_type)
+            ));
+            out.push_str("    </edge>\n");
+        }
+
+        out.push_str("  </graph>\n");
+    }
+
+    fn diagnose(
+        &self,
+        known_workflow_ids: &std::collections::HashSet<&str>,
+        out: &mut Vec<Diagnostic>,
+    ) {
+        let forward = self.adjacency(false, |_| true);
+        let backward = self.adjacency(true, |_| true);
+        let reachable_from_start = bfs(&forward, "start");
+        let can_reach_end = bfs(&backward, "end");
+
+        for node in &self.nodes {
+            if node.id != "start" && !reachable_from_start.contains(node.id.as_str()) {
+                out.push(self.diagnostic(
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "node `{}` is unreachable from the workflow's start node",
+                        node.id
+                    ),
+                    node.data.line,
+                ));
+            }
+            if node.id != "end" && !can_reach_end.contains(node.id.as_str()) {
+                out.push(self.diagnostic(
+                    DiagnosticSeverity::Warning,
+                    format!("node `{}` has no path to the workflow's end node", node.id),
+                    node.data.line,
+                ));
+            }
+            if node.data.node_kind == "workflow" {
+                if let Some(workflow_id) = &node.data.step_id {
+                    if !known_workflow_ids.contains(workflow_id.as_str()) {
+                        out.push(self.diagnostic(
+                            DiagnosticSeverity::Error,
+                            format!(
+                                "workflow call `{}` references unknown workflow `{}`",
+                                node.id, workflow_id
+                            ),
+                            node.data.line,
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mut step_id_counts: HashMap<&str, usize> = HashMap::new();
+        for node in &self.nodes {
+            if node.data.node_kind != "step" {
+                continue;
+            }
+            if let Some(step_id) = &node.data.step_id {
+                let count = step_id_counts.entry(step_id.as_str()).or_insert(0);
+                *count += 1;
+                if *count == 2 {
+                    out.push(self.diagnostic(
+                        DiagnosticSeverity::Error,
+                        format!("duplicate step_id `{}` in this workflow", step_id),
+                        node.data.line,
+                    ));
+                }
+            }
+        }
+
+        let line_by_node: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), node.data.line))
+            .collect();
+        let source_by_edge_id: HashMap<&str, &str> = self
+            .edges
+            .iter()
+            .map(|edge| (edge.id.as_str(), edge.source.as_str()))
+            .collect();
+
+        for edge_id in self.find_unmarked_cycle_edges() {
+            let line = source_by_edge_id
+                .get(edge_id.as_str())
+                .and_then(|source| line_by_node.get(source))
+                .copied()
+                .unwrap_or(0);
+            out.push(self.diagnostic(
+                DiagnosticSeverity::Error,
+                format!(
+                    "edge `{}` closes a cycle not marked as a loop-back edge",
+                    edge_id
+                ),
+                line,
+            ));
+        }
+    }
+
+    fn adjacency(
+        &self,
+        reversed: bool,
+        include: impl Fn(&GraphEdge) -> bool,
+    ) -> HashMap<&str, Vec<&str>> {
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in self.edges.iter().filter(|edge| include(edge)) {
+            let (from, to) = if reversed {
+                (edge.target.as_str(), edge.source.as_str())
+            } else {
+                (edge.source.as_str(), edge.target.as_str())
+            };
+            adjacency.entry(from).or_default().push(to);
+        }
+        adjacency
+    }
+
+    fn diagnostic(&self, severity: DiagnosticSeverity, message: String, line: usize) -> Diagnostic {
+        Diagnostic {
+            severity,
+            message,
+            file_path: self.file_path.clone(),
+            line,
+        }
+    }
+
+    // DFS over every edge not already marked "loop-back"; a back-edge (its
+    // target is still on the DFS stack) means the graph has a cycle the
+    // author didn't intend as a loop.
+    fn find_unmarked_cycle_edges(&self) -> Vec<String> {
+        let forward = self.adjacency(false, |edge| edge.edge_type != "loop-back");
+        let edge_id_by_pair: HashMap<(&str, &str), &str> = self
+            .edges
+            .iter()
+            .filter(|edge| edge.edge_type != "loop-back")
+            .map(|edge| {
+                (
+                    (edge.source.as_str(), edge.target.as_str()),
+                    edge.id.as_str(),
+                )
+            })
+            .collect();
+
+        let mut state: HashMap<&str, u8> = HashMap::new();
+        let mut cycle_edges = Vec::new();
+        for node in &self.nodes {
+            if state.get(node.id.as_str()).copied().unwrap_or(0) == 0 {
+                visit_for_cycles(
+                    &node.id,
+                    &forward,
+                    &edge_id_by_pair,
+                    &mut state,
+                    &mut cycle_edges,
+                );
+            }
+        }
+        cycle_edges
+    }
+
+    // Collapses maximal pass-through chains of plain step nodes into a
+    // single `step_chain` node, then shares structurally identical chains
+    // and workflow-call nodes across the graph. Anchors (start/end,
+    // branch/join/loop nodes, and workflow calls) are never folded, so the
+    // surviving graph still exposes the overall control-flow shape.
+    pub fn compress(&mut self) {
+        self.collapse_chains();
+        self.dedupe_subgraphs();
+    }
+
+    fn collapse_chains(&mut self) {
+        let node_kind: HashMap<String, String> = self
+            .nodes
+            .iter()
+            .map(|n| (n.id.clone(), n.data.node_kind.clone()))
+            .collect();
+        let is_step = |id: &str| node_kind.get(id).is_some_and(|kind| kind == "step");
+
+        let mut out_count: HashMap<String, usize> = HashMap::new();
+        let mut out_edge: HashMap<String, usize> = HashMap::new();
+        let mut in_count: HashMap<String, usize> = HashMap::new();
+        let mut in_edge: HashMap<String, usize> = HashMap::new();
+        for (index, edge) in self.edges.iter().enumerate() {
+            *out_count.entry(edge.source.clone()).or_insert(0) += 1;
+            out_edge.insert(edge.source.clone(), index);
+            *in_count.entry(edge.target.clone()).or_insert(0) += 1;
+            in_edge.insert(edge.target.clone(), index);
+        }
+
+        let chain_starts: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|node| is_step(&node.id))
+            .filter(|node| {
+                let predecessor_is_step = in_edge
+                    .get(&node.id)
+                    .map(|&index| &self.edges[index])
+                    .filter(|edge| {
+                        in_count.get(&node.id) == Some(&1) && edge.edge_type == "default"
+                    })
+                    .is_some_and(|edge| is_step(&edge.source));
+                !predecessor_is_step
+            })
+            .map(|node| node.id.clone())
+            .collect();
+
+        let node_by_id: HashMap<String, GraphNode> = self
+            .nodes
+            .iter()
+            .map(|n| (n.id.clone(), n.clone()))
+            .collect();
+
+        let mut folded_nodes: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut folded_edges: std::collections::HashSet<usize> = std::collections::HashSet::new();
+        let mut new_chain_nodes: Vec<GraphNode> = Vec::new();
+        let mut new_chain_edges: Vec<GraphEdge> = Vec::new();
+
+        for start_id in &chain_starts {
+            let mut members = vec![start_id.clone()];
+            let mut current = start_id.clone();
+            while let Some(next) = next_chain_member(
+                &current,
+                &out_edge,
+                &out_count,
+                &in_count,
+                &self.edges,
+                &is_step,
+            ) {
+                current = next;
+                members.push(current.clone());
+            }
+
+            if members.len() < 2 {
+                continue;
+            }
+
+            let member_nodes: Vec<&GraphNode> = members.iter().map(|id| &node_by_id[id]).collect();
+            let label = member_nodes
+                .iter()
+                .map(|node| node.data.label.as_str())
+                .collect::<Vec<_>>()
+                .join(" \u{2192} ");
+            let step_ids: Vec<String> = member_nodes
+                .iter()
+                .filter_map(|node| node.data.step_id.clone())
+                .collect();
+            let first_line = member_nodes.first().unwrap().data.line;
+            let last_line = member_nodes.last().unwrap().data.line;
+
+            let mut hasher = FnvHasher::new();
+            hasher.write(b"chain");
+            for member in &members {
+                hasher.write(member.as_bytes());
+            }
+            let chain_id = format!("chain-{}", to_base32(hasher.finish()));
+
+            new_chain_nodes.push(GraphNode {
+                id: chain_id.clone(),
+                node_type: "stepChain".to_string(),
+                position: member_nodes.first().unwrap().position.clone(),
+                data: NodeData {
+                    label,
+                    node_kind: "step_chain".to_string(),
+                    step_id: None,
+                    line: first_line,
+                    collapsed_step_ids: Some(step_ids),
+                    line_end: (last_line != first_line).then_some(last_line),
+                },
+            });
+
+            if let Some(&index) = in_edge.get(&members[0]) {
+                let incoming = &self.edges[index];
+                new_chain_edges.push(GraphEdge {
+                    id: format!("e_{}_{}", incoming.source, chain_id),
+                    source: incoming.source.clone(),
+                    target: chain_id.clone(),
+                    edge_type: incoming.edge_type.clone(),
+                });
+                folded_edges.insert(index);
+            }
+            if let Some(&index) = out_edge.get(members.last().unwrap()) {
+                let outgoing = &self.edges[index];
+                new_chain_edges.push(GraphEdge {
+                    id: format!("e_{}_{}", chain_id, outgoing.target),
+                    source: chain_id.clone(),
+                    target: outgoing.target.clone(),
+                    edge_type: outgoing.edge_type.clone(),
+                });
+                folded_edges.insert(index);
+            }
+            for window in members.windows(2) {
+                if let Some(&index) = out_edge.get(&window[0]) {
+                    folded_edges.insert(index);
+                }
+            }
+            folded_nodes.extend(members);
+        }
+
+        if new_chain_nodes.is_empty() {
+            return;
+        }
+
+        self.nodes.retain(|node| !folded_nodes.contains(&node.id));
+        self.nodes.extend(new_chain_nodes);
+
+        self.edges = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !folded_edges.contains(index))
+            .map(|(_, edge)| edge.clone())
+            .chain(new_chain_edges)
+            .collect();
+    }
+
+    // Merges nodes that are structurally identical (same node_kind, label,
+    // and step_id(s)) so a repeated workflow-call or step-chain is emitted
+    // once, with every edge that used to point at a duplicate now pointing
+    // at the shared node. Branch/join/loop/start/end anchors are left alone
+    // since their identity is tied to their position in the control flow.
+    fn dedupe_subgraphs(&mut self) {
+        let mut canonical_by_signature: HashMap<String, String> = HashMap::new();
+        let mut remap: HashMap<String, String> = HashMap::new();
+
+        for node in &self.nodes {
+            if !matches!(node.data.node_kind.as_str(), "step_chain" | "workflow") {
+                continue;
+            }
+            let signature = node_signature(node);
+            match canonical_by_signature.get(&signature) {
+                Some(canonical) => {
+                    remap.insert(node.id.clone(), canonical.clone());
+                }
+                None => {
+                    canonical_by_signature.insert(signature, node.id.clone());
+                }
+            }
+        }
+
+        if remap.is_empty() {
+            return;
+        }
+
+        self.nodes.retain(|node| !remap.contains_key(&node.id));
+
+        let mut seen_edges: std::collections::HashSet<(String, String, String)> =
+            std::collections::HashSet::new();
+        let mut deduped_edges = Vec::with_capacity(self.edges.len());
+        for edge in self.edges.drain(..) {
+            let source = remap.get(&edge.source).cloned().unwrap_or(edge.source);
+            let target = remap.get(&edge.target).cloned().unwrap_or(edge.target);
+            if source == target {
+                continue;
+            }
+            let key = (source.clone(), target.clone(), edge.edge_type.clone());
+            if !seen_edges.insert(key) {
+                continue;
+            }
+            deduped_edges.push(GraphEdge {
+                id: format!("e_{}_{}", source, target),
+                source,
+                target,
+                edge_type: edge.edge_type,
+            });
+        }
+        self.edges = deduped_edges;
+    }
+}
+
+// Returns the next node id to fold into the current step chain, or `None`
+// once the chain runs into a branch, a non-"default" edge, or a non-step
+// node.
+fn next_chain_member(
+    current: &str,
+    out_edge: &HashMap<String, usize>,
+    out_count: &HashMap<String, usize>,
+    in_count: &HashMap<String, usize>,
+    edges: &[GraphEdge],
+    is_step: &impl Fn(&str) -> bool,
+) -> Option<String> {
+    let &edge_index = out_edge.get(current)?;
+    if out_count.get(current) != Some(&1) {
+        return None;
+    }
+    let edge = &edges[edge_index];
+    if edge.edge_type != "default" || !is_step(&edge.target) {
+        return None;
+    }
+    if in_count.get(&edge.target) != Some(&1) {
+        return None;
+    }
+    Some(edge.target.clone())
+}
+
+fn node_signature(node: &GraphNode) -> String {
+    let extra = node
+        .data
+        .collapsed_step_ids
+        .as_ref()
+        .map(|ids| ids.join(","))
+        .or_else(|| node.data.step_id.clone())
+        .unwrap_or_default();
+    format!("{}|{}|{}", node.data.node_kind, node.data.label, extra)
+}
+
+fn bfs<'a>(
+    adjacency: &HashMap<&'a str, Vec<&'a str>>,
+    start: &'a str,
+) -> std::collections::HashSet<&'a str> {
+    let mut visited = std::collections::HashSet::new();
+    let mut queue = std::collections::VecDeque::new();
+    visited.insert(start);
+    queue.push_back(start);
+    while let Some(id) = queue.pop_front() {
+        for &next in adjacency.get(id).into_iter().flatten() {
+            if visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    visited
+}
+
+fn visit_for_cycles<'a>(
+    id: &'a str,
+    forward: &HashMap<&'a str, Vec<&'a str>>,
+    edge_id_by_pair: &HashMap<(&'a str, &'a str), &'a str>,
+    state: &mut HashMap<&'a str, u8>,
+    cycle_edges: &mut Vec<String>,
+) {
+    state.insert(id, 1); // in progress
+    for &next in forward.get(id).into_iter().flatten() {
+        match state.get(next).copied().unwrap_or(0) {
+            0 => visit_for_cycles(next, forward, edge_id_by_pair, state, cycle_edges),
+            1 => {
+                if let Some(&edge_id) = edge_id_by_pair.get(&(id, next)) {
+                    cycle_edges.push(edge_id.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    state.insert(id, 2); // done
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Reorders `layer` in place by the median index of each node's neighbors
+// (via `adjacency`) within `neighbor_layer`. Nodes with no neighbors in that
+// direction use their current slot as their sort key so they don't all get
+// shuffled to one end; they can still move when other nodes sort past them.
+fn reorder_by_median(
+    layer: &mut Vec<String>,
+    neighbor_layer: &[String],
+    adjacency: &HashMap<&str, Vec<&str>>,
+) {
+    let neighbor_slot: HashMap<&str, usize> = neighbor_layer
+        .iter()
+        .enumerate()
+        .map(|(slot, id)| (id.as_str(), slot))
+        .collect();
+
+    let mut medians: Vec<(String, f64)> = layer
+        .iter()
+        .enumerate()
+        .map(|(current_slot, id)| {
+            let mut positions: Vec<usize> = adjacency
+                .get(id.as_str())
+                .into_iter()
+                .flatten()
+                .filter_map(|neighbor| neighbor_slot.get(neighbor).copied())
+                .collect();
+            positions.sort_unstable();
+
+            let median = if positions.is_empty() {
+                current_slot as f64
+            } else if positions.len() % 2 == 1 {
+                positions[positions.len() / 2] as f64
+            } else {
+                let mid = positions.len() / 2;
+                (positions[mid - 1] as f64 + positions[mid] as f64) / 2.0
+            };
+
+            (id.clone(), median)
+        })
+        .collect();
+
+    medians.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    *layer = medians.into_iter().map(|(id, _)| id).collect();
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GraphNode {
@@ -42,6 +810,13 @@ pub struct NodeData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub step_id: Option<String>,
     pub line: usize,
+    // Populated on a "step_chain" node produced by `WorkflowGraph::compress`:
+    // the step_ids of the collapsed chain members, in order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collapsed_step_ids: Option<Vec<String>>,
+    // End line of a collapsed chain, when it spans more than one line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_end: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -54,13 +829,39 @@ pub struct GraphEdge {
     pub edge_type: String,
 }
 
+// Tracks an open branch/join construct (conditional, parallel, or loop) while
+// its body is being visited, so the branches can reconverge onto a shared
+// successor node instead of the single linear `prev_node_id`.
+#[derive(Debug)]
+struct JoinPoint {
+    entry_node_id: String,
+    // (tip node ID, edge_type to wire into the join). An empty arm's tip is
+    // the construct's own entry node, wired with the arm's `begin_branch`
+    // edge_type (e.g. "false") rather than "default", so the empty path
+    // keeps its semantic label.
+    branch_tips: Vec<(String, String)>,
+}
+
+// Whether `GraphBuilder::to_manifest` should fold pass-through step chains
+// and share duplicate subgraphs, or leave every node as built for full
+// detail.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionMode {
+    #[default]
+    Full,
+    Compressed,
+}
+
 #[derive(Debug)]
 pub struct GraphBuilder {
     graphs: HashMap<String, WorkflowGraph>,
     current_workflow: Option<String>,
     current_y: f64,
-    node_count: usize,
     prev_node_id: Option<String>,
+    pending_edge_type: Option<String>,
+    join_stack: Vec<JoinPoint>,
+    id_seen: HashMap<String, usize>,
+    compression: CompressionMode,
 }
 
 impl GraphBuilder {
@@ -69,8 +870,11 @@ impl GraphBuilder {
             graphs: HashMap::new(),
             current_workflow: None,
             current_y: 0.0,
-            node_count: 0,
             prev_node_id: None,
+            pending_edge_type: None,
+            join_stack: Vec::new(),
+            id_seen: HashMap::new(),
+            compression: CompressionMode::Full,
         }
     }
 
@@ -86,8 +890,10 @@ impl GraphBuilder {
         self.graphs.insert(name.to_string(), graph);
         self.current_workflow = Some(name.to_string());
         self.current_y = 0.0;
-        self.node_count = 0;
         self.prev_node_id = None;
+        self.pending_edge_type = None;
+        self.join_stack.clear();
+        self.id_seen.clear();
 
         // Add start node
         self.add_node(
@@ -100,8 +906,71 @@ impl GraphBuilder {
         );
     }
 
+    // Looks up a predecessor's own node_kind/label/step_id rather than its
+    // (already content-hashed) ID, so that hash doesn't transitively depend
+    // on *its* predecessor. See `content_id` for why that distinction
+    // matters.
+    fn local_signature(&self, node_id: &str) -> String {
+        let node = self
+            .current_workflow
+            .as_ref()
+            .and_then(|name| self.graphs.get(name))
+            .and_then(|graph| graph.nodes.iter().find(|node| node.id == node_id));
+        match node {
+            Some(node) => format!(
+                "{}\u{1}{}\u{1}{}",
+                node.data.node_kind,
+                node.data.label,
+                node.data.step_id.as_deref().unwrap_or("")
+            ),
+            None => node_id.to_string(),
+        }
+    }
+
+    // Derives a stable node ID from the node's own attributes and each
+    // predecessor's *local signature* (its own node_kind/label/step_id), not
+    // the predecessor's ID. Folding in the ID would transitively pull in the
+    // predecessor's own predecessor, and so on back to `start`, so a step
+    // inserted anywhere upstream would change every node below it. Folding
+    // in the local signature instead means only the node immediately after
+    // an insertion point picks up a new ID (its immediate predecessor really
+    // did change); every node further downstream still sees the same
+    // unchanged signature from its own predecessor and keeps its ID.
+    // Genuine collisions (same attributes, same predecessor signatures) are
+    // disambiguated with a numeric suffix via `id_seen`.
+    fn content_id(
+        &mut self,
+        node_kind: &str,
+        label: &str,
+        step_id: Option<&str>,
+        predecessors: &[String],
+    ) -> String {
+        let mut hasher = FnvHasher::new();
+        hasher.write(node_kind.as_bytes());
+        hasher.write(label.as_bytes());
+        hasher.write(step_id.unwrap_or("").as_bytes());
+        for predecessor in predecessors {
+            hasher.write(self.local_signature(predecessor).as_bytes());
+        }
+
+        let base = format!("step-{}", to_base32(hasher.finish()));
+        let seen = *self.id_seen.get(&base).unwrap_or(&0);
+        self.id_seen.insert(base.clone(), seen + 1);
+
+        if seen == 0 {
+            base
+        } else {
+            format!("{}-{}", base, seen)
+        }
+    }
+
+    fn prev_as_predecessors(&self) -> Vec<String> {
+        self.prev_node_id.clone().into_iter().collect()
+    }
+
     pub fn add_step_node(&mut self, step_name: &str, step_id: &str, line: usize) {
-        let node_id = format!("node_{}", self.node_count);
+        let preds = self.prev_as_predecessors();
+        let node_id = self.content_id("step", step_name, Some(step_id), &preds);
         self.add_node(
             &node_id,
             "step",
@@ -113,7 +982,8 @@ impl GraphBuilder {
     }
 
     pub fn add_workflow_node(&mut self, workflow_name: &str, workflow_id: &str, line: usize) {
-        let node_id = format!("node_{}", self.node_count);
+        let preds = self.prev_as_predecessors();
+        let node_id = self.content_id("workflow", workflow_name, Some(workflow_id), &preds);
         self.add_node(
             &node_id,
             "workflowCall",
@@ -124,6 +994,113 @@ impl GraphBuilder {
         );
     }
 
+    // Opens a conditional (`if`/`else`) construct: emits the branch node and
+    // pushes a join point that each `begin_branch`/`end_branch` pair will
+    // register a tip against.
+    pub fn start_conditional(&mut self, label: &str, line: usize) {
+        let preds = self.prev_as_predecessors();
+        let node_id = self.content_id("conditional", label, None, &preds);
+        self.add_node(&node_id, "conditional", label, "conditional", None, line);
+        self.join_stack.push(JoinPoint {
+            entry_node_id: node_id,
+            branch_tips: vec![],
+        });
+    }
+
+    // Begins one arm of the innermost open branch/parallel construct. The
+    // next node added will hang off the construct's entry node via an edge
+    // carrying `edge_type` (e.g. "true", "false", "parallel").
+    pub fn begin_branch(&mut self, edge_type: &str) {
+        if let Some(join) = self.join_stack.last() {
+            self.prev_node_id = Some(join.entry_node_id.clone());
+        }
+        self.pending_edge_type = Some(edge_type.to_string());
+    }
+
+    // Closes the current arm, recording its tip node (and the edge_type it
+    // should be wired into the join with) so it can be wired into the
+    // eventual join node. `add_node` already consumes `pending_edge_type`
+    // for any node added inside the arm, so by the time we get here it's
+    // still set only when the arm added no node of its own — in which case
+    // it holds the arm's own edge_type (e.g. "false") rather than the
+    // "default" a populated arm's last edge would carry. Always taking it
+    // also clears it so an empty arm can't leak its edge type onto the next
+    // node added after the join.
+    pub fn end_branch(&mut self) {
+        let edge_type = self
+            .pending_edge_type
+            .take()
+            .unwrap_or_else(|| "default".to_string());
+        if let Some(tip) = self.prev_node_id.take() {
+            if let Some(join) = self.join_stack.last_mut() {
+                join.branch_tips.push((tip, edge_type));
+            }
+        }
+    }
+
+    pub fn end_conditional(&mut self, line: usize) {
+        self.close_join("Join", "join", line);
+    }
+
+    // Opens a `Promise.all`-style parallel construct.
+    pub fn start_parallel(&mut self, label: &str, line: usize) {
+        let preds = self.prev_as_predecessors();
+        let node_id = self.content_id("parallel", label, None, &preds);
+        self.add_node(&node_id, "parallel", label, "parallel", None, line);
+        self.join_stack.push(JoinPoint {
+            entry_node_id: node_id,
+            branch_tips: vec![],
+        });
+    }
+
+    pub fn begin_parallel_branch(&mut self) {
+        self.begin_branch("parallel");
+    }
+
+    pub fn end_parallel_branch(&mut self) {
+        self.end_branch();
+    }
+
+    pub fn end_parallel(&mut self, line: usize) {
+        self.close_join("Join", "join", line);
+    }
+
+    fn close_join(&mut self, label: &str, node_kind: &str, line: usize) {
+        let Some(join) = self.join_stack.pop() else {
+            return;
+        };
+        let tip_ids: Vec<String> = join
+            .branch_tips
+            .iter()
+            .map(|(tip, _)| tip.clone())
+            .collect();
+        let node_id = self.content_id(node_kind, label, None, &tip_ids);
+        self.add_join_node(&node_id, label, node_kind, line, join.branch_tips);
+    }
+
+    // Opens a `for`/`while` loop: the loop node doubles as the join that the
+    // loop body edge ("loop-back") and the exit edge both pass through.
+    pub fn start_loop(&mut self, label: &str, line: usize) {
+        let preds = self.prev_as_predecessors();
+        let node_id = self.content_id("loop", label, None, &preds);
+        self.add_node(&node_id, "loop", label, "loop", None, line);
+        self.join_stack.push(JoinPoint {
+            entry_node_id: node_id.clone(),
+            branch_tips: vec![],
+        });
+        self.prev_node_id = Some(node_id);
+    }
+
+    pub fn end_loop(&mut self) {
+        let Some(join) = self.join_stack.pop() else {
+            return;
+        };
+        if let Some(tip) = self.prev_node_id.take() {
+            self.link_edge(&tip, &join.entry_node_id, "loop-back");
+        }
+        self.prev_node_id = Some(join.entry_node_id);
+    }
+
     fn add_node(
         &mut self,
         id: &str,
@@ -133,40 +1110,112 @@ impl GraphBuilder {
         step_id: Option<String>,
         line: usize,
     ) {
-        if let Some(workflow_name) = &self.current_workflow {
-            if let Some(graph) = self.graphs.get_mut(workflow_name) {
-                let node = GraphNode {
-                    id: id.to_string(),
-                    node_type: node_type.to_string(),
-                    position: Position {
-                        x: 250.0,
-                        y: self.current_y,
-                    },
-                    data: NodeData {
-                        label: label.to_string(),
-                        node_kind: node_kind.to_string(),
-                        step_id,
-                        line,
-                    },
-                };
+        let Some(workflow_name) = self.current_workflow.clone() else {
+            return;
+        };
+        let Some(graph) = self.graphs.get_mut(&workflow_name) else {
+            return;
+        };
 
-                // Add edge from previous node
-                if let Some(prev_id) = &self.prev_node_id {
-                    let edge = GraphEdge {
-                        id: format!("e_{}_{}", prev_id, id),
-                        source: prev_id.clone(),
-                        target: id.to_string(),
-                        edge_type: "default".to_string(),
-                    };
-                    graph.edges.push(edge);
-                }
+        let node = GraphNode {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            position: Position {
+                x: 250.0,
+                y: self.current_y,
+            },
+            data: NodeData {
+                label: label.to_string(),
+                node_kind: node_kind.to_string(),
+                step_id,
+                line,
+                collapsed_step_ids: None,
+                line_end: None,
+            },
+        };
 
-                graph.nodes.push(node);
-                self.prev_node_id = Some(id.to_string());
-                self.current_y += 100.0;
-                self.node_count += 1;
-            }
+        if let Some(prev_id) = self.prev_node_id.clone() {
+            let edge_type = self
+                .pending_edge_type
+                .take()
+                .unwrap_or_else(|| "default".to_string());
+            graph.edges.push(GraphEdge {
+                id: format!("e_{}_{}", prev_id, id),
+                source: prev_id,
+                target: id.to_string(),
+                edge_type,
+            });
+        } else {
+            self.pending_edge_type = None;
+        }
+
+        graph.nodes.push(node);
+        self.prev_node_id = Some(id.to_string());
+        self.current_y += 100.0;
+    }
+
+    // Like `add_node`, but wires in an explicit set of (parent, edge_type)
+    // pairs instead of the single `prev_node_id`, since a join node can have
+    // more than one incoming edge.
+    fn add_join_node(
+        &mut self,
+        id: &str,
+        label: &str,
+        node_kind: &str,
+        line: usize,
+        parents: Vec<(String, String)>,
+    ) {
+        let Some(workflow_name) = self.current_workflow.clone() else {
+            return;
+        };
+        let Some(graph) = self.graphs.get_mut(&workflow_name) else {
+            return;
+        };
+
+        let node = GraphNode {
+            id: id.to_string(),
+            node_type: "join".to_string(),
+            position: Position {
+                x: 250.0,
+                y: self.current_y,
+            },
+            data: NodeData {
+                label: label.to_string(),
+                node_kind: node_kind.to_string(),
+                step_id: None,
+                line,
+                collapsed_step_ids: None,
+                line_end: None,
+            },
+        };
+
+        for (parent, edge_type) in parents {
+            graph.edges.push(GraphEdge {
+                id: format!("e_{}_{}", parent, id),
+                source: parent,
+                target: id.to_string(),
+                edge_type,
+            });
         }
+
+        graph.nodes.push(node);
+        self.prev_node_id = Some(id.to_string());
+        self.current_y += 100.0;
+    }
+
+    fn link_edge(&mut self, source: &str, target: &str, edge_type: &str) {
+        let Some(workflow_name) = self.current_workflow.clone() else {
+            return;
+        };
+        let Some(graph) = self.graphs.get_mut(&workflow_name) else {
+            return;
+        };
+        graph.edges.push(GraphEdge {
+            id: format!("e_{}_{}", source, target),
+            source: source.to_string(),
+            target: target.to_string(),
+            edge_type: edge_type.to_string(),
+        });
     }
 
     pub fn finish_workflow(&mut self) {
@@ -185,6 +1234,8 @@ impl GraphBuilder {
                         node_kind: "workflow_end".to_string(),
                         step_id: None,
                         line: 0,
+                        collapsed_step_ids: None,
+                        line_end: None,
                     },
                 };
 
@@ -205,9 +1256,25 @@ impl GraphBuilder {
 
         self.current_workflow = None;
         self.prev_node_id = None;
+        self.pending_edge_type = None;
+        self.join_stack.clear();
     }
 
-    pub fn to_manifest(self) -> WorkflowGraphManifest {
+    // Lets callers opt into a compressed overview (folded step chains,
+    // shared duplicate subgraphs) instead of the fully detailed graph.
+    pub fn with_compression(mut self, mode: CompressionMode) -> Self {
+        self.compression = mode;
+        self
+    }
+
+    pub fn to_manifest(mut self) -> WorkflowGraphManifest {
+        for graph in self.graphs.values_mut() {
+            if self.compression == CompressionMode::Compressed {
+                graph.compress();
+            }
+            graph.layout();
+        }
+
         WorkflowGraphManifest {
             version: "1.0.0".to_string(),
             workflows: self.graphs,
@@ -218,3 +1285,44 @@ impl GraphBuilder {
         !self.graphs.is_empty()
     }
 }
+
+// FNV-1a, with a byte written between `write` calls so that e.g. ("ab", "c")
+// and ("a", "bc") don't hash the same.
+struct FnvHasher(u64);
+
+impl FnvHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+        self.0 ^= 0xff;
+        self.0 = self.0.wrapping_mul(Self::PRIME);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+const BASE32_ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+fn to_base32(mut value: u64) -> String {
+    if value == 0 {
+        return "0".to_string();
+    }
+
+    let mut digits = Vec::new();
+    while value > 0 {
+        digits.push(BASE32_ALPHABET[(value & 0x1f) as usize] as char);
+        value >>= 5;
+    }
+    digits.iter().rev().collect()
+}
@@ -0,0 +1,5 @@
+pub mod graph;
+mod transform;
+
+pub use graph::{CompressionMode, WorkflowGraphManifest};
+pub use transform::{StepTransform, TransformMode};
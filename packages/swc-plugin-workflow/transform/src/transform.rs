@@ -0,0 +1,306 @@
+use crate::graph::{CompressionMode, GraphBuilder};
+use swc_core::common::sync::Lrc;
+use swc_core::common::{SourceMap, Span, DUMMY_SP};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::VisitMut;
+
+/// What `StepTransform` should produce from the workflows it finds in a
+/// module. `Graph` feeds the full React-Flow-style manifest as a JSON string;
+/// `Dot`/`GraphML` render the same graph as text for the wider graph-tooling
+/// ecosystem; `GraphCompressed` folds pass-through step chains and shares
+/// duplicate subgraphs before emitting the manifest; `Diagnostics` runs the
+/// validation pass instead of emitting the graph itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransformMode {
+    Graph,
+    GraphCompressed,
+    Dot,
+    GraphML,
+    Diagnostics,
+}
+
+/// Walks a module looking for `defineWorkflow("name", async (ctx) => { ... })`
+/// calls and records their control flow into a `GraphBuilder`, then appends
+/// the requested rendering of the resulting manifest as an exported constant
+/// so the output stays valid, diffable JS.
+pub struct StepTransform {
+    mode: TransformMode,
+    file_path: String,
+    cm: Lrc<SourceMap>,
+    builder: GraphBuilder,
+}
+
+impl StepTransform {
+    // `cm` must be the `SourceMap` the module was parsed against (the plugin
+    // host hands one over via `TransformPluginProgramMetadata::source_map`;
+    // the fixture harness hands over `Tester::cm`), so `line_of` can resolve
+    // a `Span`'s `BytePos` to the real 1-based source line rather than
+    // reporting a raw byte offset.
+    pub fn new(mode: TransformMode, file_path: String, cm: Lrc<SourceMap>) -> Self {
+        let compression = match mode {
+            TransformMode::GraphCompressed => CompressionMode::Compressed,
+            _ => CompressionMode::Full,
+        };
+        Self {
+            mode,
+            file_path,
+            cm,
+            builder: GraphBuilder::new().with_compression(compression),
+        }
+    }
+
+    fn line_of(&self, span: Span) -> usize {
+        self.cm.lookup_char_pos(span.lo()).line
+    }
+
+    fn workflow_name(callee: &Callee) -> Option<&str> {
+        let Callee::Expr(expr) = callee else {
+            return None;
+        };
+        match &**expr {
+            Expr::Ident(ident) if &*ident.sym == "defineWorkflow" => Some("defineWorkflow"),
+            _ => None,
+        }
+    }
+
+    fn string_arg(args: &[ExprOrSpread], index: usize) -> Option<String> {
+        let arg = args.get(index)?;
+        match &*arg.expr {
+            Expr::Lit(Lit::Str(s)) => Some(s.value.as_wtf8().to_string_lossy().into_owned()),
+            _ => None,
+        }
+    }
+
+    fn callback_body(args: &[ExprOrSpread]) -> Option<&BlockStmtOrExpr> {
+        match args.last()?.expr.as_ref() {
+            Expr::Arrow(arrow) => Some(&arrow.body),
+            _ => None,
+        }
+    }
+
+    fn step_call(expr: &Expr) -> Option<(&str, &CallExpr)> {
+        let Expr::Call(call) = expr else {
+            return None;
+        };
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let Expr::Member(member) = &**callee else {
+            return None;
+        };
+        let MemberProp::Ident(prop) = &member.prop else {
+            return None;
+        };
+        let Expr::Ident(obj) = &*member.obj else {
+            return None;
+        };
+        if &*obj.sym != "ctx" {
+            return None;
+        }
+        match &*prop.sym {
+            "step" => Some(("step", call)),
+            "workflow" => Some(("workflow", call)),
+            _ => None,
+        }
+    }
+
+    fn visit_workflow_body(&mut self, body: &BlockStmtOrExpr) {
+        match body {
+            BlockStmtOrExpr::BlockStmt(block) => {
+                for stmt in &block.stmts {
+                    self.visit_workflow_stmt(stmt);
+                }
+            }
+            BlockStmtOrExpr::Expr(expr) => self.visit_workflow_expr(expr),
+        }
+    }
+
+    fn visit_workflow_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expr(expr_stmt) => self.visit_workflow_expr(&expr_stmt.expr),
+            Stmt::If(if_stmt) => self.visit_if(if_stmt),
+            Stmt::For(for_stmt) => self.visit_loop(self.line_of(for_stmt.span), &for_stmt.body),
+            Stmt::While(while_stmt) => {
+                self.visit_loop(self.line_of(while_stmt.span), &while_stmt.body)
+            }
+            Stmt::DoWhile(do_stmt) => self.visit_loop(self.line_of(do_stmt.span), &do_stmt.body),
+            Stmt::Block(block) => {
+                for inner in &block.stmts {
+                    self.visit_workflow_stmt(inner);
+                }
+            }
+            Stmt::Decl(Decl::Var(var_decl)) => {
+                for decl in &var_decl.decls {
+                    if let Some(init) = &decl.init {
+                        self.visit_workflow_expr(init);
+                    }
+                }
+            }
+            Stmt::Return(ret) => {
+                if let Some(arg) = &ret.arg {
+                    self.visit_workflow_expr(arg);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_workflow_expr(&mut self, expr: &Expr) {
+        let unwrapped = match expr {
+            Expr::Await(await_expr) => &*await_expr.arg,
+            other => other,
+        };
+
+        if let Some((kind, call)) = Self::step_call(unwrapped) {
+            let Some(name) = Self::string_arg(&call.args, 0) else {
+                return;
+            };
+            let line = self.line_of(call.span);
+            match kind {
+                "step" => self.builder.add_step_node(&name, &name, line),
+                "workflow" => self.builder.add_workflow_node(&name, &name, line),
+                _ => {}
+            }
+            return;
+        }
+
+        if Self::is_promise_all(unwrapped) {
+            self.visit_parallel(unwrapped);
+        }
+    }
+
+    fn is_promise_all(expr: &Expr) -> bool {
+        let Expr::Call(call) = expr else {
+            return false;
+        };
+        let Callee::Expr(callee) = &call.callee else {
+            return false;
+        };
+        let Expr::Member(member) = &**callee else {
+            return false;
+        };
+        let (Expr::Ident(obj), MemberProp::Ident(prop)) = (&*member.obj, &member.prop) else {
+            return false;
+        };
+        &*obj.sym == "Promise" && &*prop.sym == "all"
+    }
+
+    fn visit_parallel(&mut self, expr: &Expr) {
+        let Expr::Call(call) = expr else { return };
+        let Some(Expr::Array(array)) = call.args.first().map(|a| &*a.expr) else {
+            return;
+        };
+
+        self.builder
+            .start_parallel("Parallel", self.line_of(call.span));
+        for elem in array.elems.iter().flatten() {
+            self.builder.begin_parallel_branch();
+            self.visit_workflow_expr(&elem.expr);
+            self.builder.end_parallel_branch();
+        }
+        self.builder.end_parallel(self.line_of(call.span));
+    }
+
+    fn visit_if(&mut self, if_stmt: &IfStmt) {
+        self.builder
+            .start_conditional("If", self.line_of(if_stmt.span));
+
+        self.builder.begin_branch("true");
+        self.visit_workflow_stmt(&if_stmt.cons);
+        self.builder.end_branch();
+
+        self.builder.begin_branch("false");
+        if let Some(alt) = &if_stmt.alt {
+            self.visit_workflow_stmt(alt);
+        }
+        self.builder.end_branch();
+
+        self.builder.end_conditional(self.line_of(if_stmt.span));
+    }
+
+    fn visit_loop(&mut self, line: usize, body: &Stmt) {
+        self.builder.start_loop("Loop", line);
+        self.visit_workflow_stmt(body);
+        self.builder.end_loop();
+    }
+
+    fn render(&mut self) -> String {
+        let builder = std::mem::replace(&mut self.builder, GraphBuilder::new());
+        let manifest = builder.to_manifest();
+        match self.mode {
+            TransformMode::Graph | TransformMode::GraphCompressed => {
+                serde_json::to_string(&manifest).unwrap_or_default()
+            }
+            TransformMode::Dot => manifest.to_dot(),
+            TransformMode::GraphML => manifest.to_graphml(),
+            TransformMode::Diagnostics => {
+                serde_json::to_string(&manifest.diagnostics()).unwrap_or_default()
+            }
+        }
+    }
+}
+
+impl VisitMut for StepTransform {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        let items = std::mem::take(&mut module.body);
+        for item in &items {
+            let var_decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => var_decl,
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match &export.decl {
+                    Decl::Var(var_decl) => var_decl,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            for decl in &var_decl.decls {
+                let Some(init) = &decl.init else { continue };
+                let Expr::Call(call) = &**init else { continue };
+                if Self::workflow_name(&call.callee).is_none() {
+                    continue;
+                }
+                let Some(name) = Self::string_arg(&call.args, 0) else {
+                    continue;
+                };
+                let Some(body) = Self::callback_body(&call.args) else {
+                    continue;
+                };
+
+                self.builder.start_workflow(&name, &self.file_path, &name);
+                self.visit_workflow_body(body);
+                self.builder.finish_workflow();
+            }
+        }
+
+        let mut items = items;
+        if !self.builder.has_workflows() {
+            module.body = items;
+            return;
+        }
+
+        let rendered = self.render();
+        items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span: DUMMY_SP,
+            decl: Decl::Var(Box::new(VarDecl {
+                span: DUMMY_SP,
+                ctxt: Default::default(),
+                kind: VarDeclKind::Const,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(BindingIdent {
+                        id: Ident::new_no_ctxt("__workflowGraph".into(), DUMMY_SP),
+                        type_ann: None,
+                    }),
+                    init: Some(Box::new(Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: rendered.into(),
+                        raw: None,
+                    })))),
+                    definite: false,
+                }],
+            })),
+        })));
+
+        module.body = items;
+    }
+}
@@ -10,10 +10,11 @@ fn graph_mode(input: PathBuf) {
     let graph_output = input.parent().unwrap().join("output-graph.js");
     test_fixture(
         Default::default(),
-        &|_| {
+        &|tester| {
             visit_mut_pass(StepTransform::new(
                 TransformMode::Graph,
                 input.file_name().unwrap().to_string_lossy().to_string(),
+                tester.cm.clone(),
             ))
         },
         &input,
@@ -24,3 +25,87 @@ fn graph_mode(input: PathBuf) {
         },
     );
 }
+
+#[testing::fixture("tests/graph-compressed/**/input.js")]
+fn graph_compressed_mode(input: PathBuf) {
+    let graph_output = input.parent().unwrap().join("output-graph-compressed.js");
+    test_fixture(
+        Default::default(),
+        &|tester| {
+            visit_mut_pass(StepTransform::new(
+                TransformMode::GraphCompressed,
+                input.file_name().unwrap().to_string_lossy().to_string(),
+                tester.cm.clone(),
+            ))
+        },
+        &input,
+        &graph_output,
+        FixtureTestConfig {
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[testing::fixture("tests/dot/**/input.js")]
+fn dot_mode(input: PathBuf) {
+    let dot_output = input.parent().unwrap().join("output-dot.js");
+    test_fixture(
+        Default::default(),
+        &|tester| {
+            visit_mut_pass(StepTransform::new(
+                TransformMode::Dot,
+                input.file_name().unwrap().to_string_lossy().to_string(),
+                tester.cm.clone(),
+            ))
+        },
+        &input,
+        &dot_output,
+        FixtureTestConfig {
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[testing::fixture("tests/graphml/**/input.js")]
+fn graphml_mode(input: PathBuf) {
+    let graphml_output = input.parent().unwrap().join("output-graphml.js");
+    test_fixture(
+        Default::default(),
+        &|tester| {
+            visit_mut_pass(StepTransform::new(
+                TransformMode::GraphML,
+                input.file_name().unwrap().to_string_lossy().to_string(),
+                tester.cm.clone(),
+            ))
+        },
+        &input,
+        &graphml_output,
+        FixtureTestConfig {
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[testing::fixture("tests/diagnostics/**/input.js")]
+fn diagnostics_mode(input: PathBuf) {
+    let diagnostics_output = input.parent().unwrap().join("output-diagnostics.js");
+    test_fixture(
+        Default::default(),
+        &|tester| {
+            visit_mut_pass(StepTransform::new(
+                TransformMode::Diagnostics,
+                input.file_name().unwrap().to_string_lossy().to_string(),
+                tester.cm.clone(),
+            ))
+        },
+        &input,
+        &diagnostics_output,
+        FixtureTestConfig {
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}